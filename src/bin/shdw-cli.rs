@@ -0,0 +1,184 @@
+use byte_unit::Byte;
+use clap::{Parser, Subcommand};
+use shadow_drive_rust::client::transaction_options::{PriorityFee, TransactionOptions};
+use shadow_drive_rust::ShadowDriveClient;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::keypair::read_keypair_file;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Command-line front end for the shadow-drive-rust SDK.
+#[derive(Parser)]
+#[command(name = "shdw-cli", author, version, about)]
+struct Cli {
+    /// Path to the keypair used to sign and pay for transactions.
+    #[arg(long, global = true)]
+    keypair: PathBuf,
+
+    /// Solana RPC endpoint to submit transactions to.
+    #[arg(long, global = true, default_value = "https://ssc-dao.genesysgo.net")]
+    rpc_url: String,
+
+    /// Commitment level used to fetch the blockhash and confirm transactions. Only applied by
+    /// reduce-storage and claim-stake.
+    #[arg(long, global = true, default_value = "confirmed")]
+    commitment: String,
+
+    /// Compute-unit price, in micro-lamports, prepended as a priority fee. Only applied by
+    /// reduce-storage and claim-stake.
+    #[arg(long, global = true)]
+    priority_fee: Option<u64>,
+
+    /// Simulate the transaction instead of submitting it.
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Reduce the storage reserved by an existing storage account.
+    ReduceStorage {
+        /// Public key of the storage account to shrink.
+        storage_account: String,
+        /// Amount of storage to remove, e.g. "1MB".
+        size: String,
+    },
+    /// Claim accrued stake rewards for an existing storage account.
+    ClaimStake {
+        /// Public key of the storage account whose rewards will be claimed.
+        storage_account: String,
+    },
+    /// Create a new storage account.
+    CreateStorageAccount {
+        /// Human-readable name for the storage account.
+        name: String,
+        /// Amount of storage to reserve, e.g. "1GB".
+        size: String,
+    },
+    /// Upload a file to an existing storage account.
+    Upload {
+        /// Public key of the storage account to upload to.
+        storage_account: String,
+        /// Path of the file to upload.
+        file: PathBuf,
+    },
+    /// Print the on-chain state of a storage account.
+    GetStorageAccount {
+        /// Public key of the storage account to look up.
+        storage_account: String,
+    },
+}
+
+/// Prints the outcome of a `--dry-run` simulation: the transaction error, if any, followed by
+/// the program logs the RPC node captured while simulating it.
+fn print_simulation_result(result: &RpcSimulateTransactionResult) {
+    match &result.err {
+        Some(err) => println!("simulation failed: {err}"),
+        None => println!("simulation succeeded"),
+    }
+    if let Some(logs) = &result.logs {
+        for log in logs {
+            println!("{log}");
+        }
+    }
+}
+
+fn parse_commitment(commitment: &str) -> CommitmentConfig {
+    CommitmentConfig::from_str(commitment).unwrap_or_else(|_| {
+        eprintln!("unrecognized commitment `{commitment}`, defaulting to confirmed");
+        CommitmentConfig::confirmed()
+    })
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let keypair = read_keypair_file(&cli.keypair)
+        .map_err(|err| anyhow::anyhow!("failed to load keypair at {:?}: {err}", cli.keypair))?;
+    let rpc_client = RpcClient::new(cli.rpc_url.clone());
+    let client = ShadowDriveClient::new(keypair, rpc_client);
+
+    let options = TransactionOptions {
+        commitment: parse_commitment(&cli.commitment),
+        priority_fee: cli.priority_fee.map(|compute_unit_price| PriorityFee {
+            compute_unit_price,
+            compute_unit_limit: None,
+        }),
+        max_retries: 3,
+    };
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async move {
+        match cli.command {
+            Command::ReduceStorage {
+                storage_account,
+                size,
+            } => {
+                let storage_account_key = Pubkey::from_str(&storage_account)?;
+                let size = Byte::from_str(&size).map_err(|err| anyhow::anyhow!("{err}"))?;
+                let ix = client
+                    .reduce_storage_ix(&storage_account_key, size)
+                    .await?;
+
+                if cli.dry_run {
+                    let result = client.simulate_instructions(vec![ix], &options)?;
+                    print_simulation_result(&result);
+                } else {
+                    let response = client
+                        .reduce_storage_with_options(&storage_account_key, size, &options)
+                        .await?;
+                    println!("txid: {}", response.txid);
+                }
+            }
+            Command::ClaimStake { storage_account } => {
+                let storage_account_key = Pubkey::from_str(&storage_account)?;
+                let ix = client.claim_stake_ix(&storage_account_key).await?;
+
+                if cli.dry_run {
+                    let result = client.simulate_instructions(vec![ix], &options)?;
+                    print_simulation_result(&result);
+                } else {
+                    let response = client
+                        .claim_stake_with_options(&storage_account_key, &options)
+                        .await?;
+                    println!("txid: {}", response.txid);
+                }
+            }
+            Command::CreateStorageAccount { name, size } => {
+                if cli.dry_run {
+                    anyhow::bail!("--dry-run is not supported for create-storage-account");
+                }
+                let size = Byte::from_str(&size).map_err(|err| anyhow::anyhow!("{err}"))?;
+                let response = client.create_storage_account(&name, size).await?;
+                println!("txid: {}", response.txid);
+            }
+            Command::Upload {
+                storage_account,
+                file,
+            } => {
+                if cli.dry_run {
+                    anyhow::bail!("--dry-run is not supported for upload");
+                }
+                let storage_account_key = Pubkey::from_str(&storage_account)?;
+                let response = client.upload_file(&storage_account_key, &file).await?;
+                println!("{response:#?}");
+            }
+            Command::GetStorageAccount { storage_account } => {
+                if cli.dry_run {
+                    eprintln!("get-storage-account is read-only; --dry-run has no effect");
+                }
+                let storage_account_key = Pubkey::from_str(&storage_account)?;
+                let account = client.get_storage_account(&storage_account_key).await?;
+                println!("{account:#?}");
+            }
+        }
+
+        Ok::<(), anyhow::Error>(())
+    })
+}
@@ -1,13 +1,11 @@
 use anchor_lang::{system_program, InstructionData, ToAccountMetas};
 use shadow_drive_user_staking::accounts as shdw_drive_accounts;
 use shadow_drive_user_staking::instruction::ClaimStake;
-use solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel};
-use solana_sdk::{
-    instruction::Instruction, pubkey::Pubkey, signer::Signer, transaction::Transaction,
-};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
 use spl_associated_token_account::get_associated_token_address;
 
-use super::Client;
+use super::transaction_options::TransactionOptions;
+use super::ShadowDriveClient;
 use crate::{
     constants::{PROGRAM_ADDRESS, STORAGE_CONFIG_PDA, TOKEN_MINT},
     derived_addresses::*,
@@ -15,7 +13,7 @@ use crate::{
 };
 use spl_token::ID as TokenProgramID;
 
-impl<T> Client<T>
+impl<T> ShadowDriveClient<T>
 where
     T: Signer + Send + Sync,
 {
@@ -23,52 +21,151 @@ where
         &self,
         storage_account_key: &Pubkey,
     ) -> ShadowDriveResult<ShdwDriveResponse> {
-        let wallet = &self.wallet;
-        let wallet_pubkey = wallet.pubkey();
+        self.claim_stake_with_options(storage_account_key, &TransactionOptions::default())
+            .await
+    }
+
+    /// Same as [`claim_stake`][Self::claim_stake], but lets the caller override the commitment
+    /// level, attach a priority fee, and configure blockhash-expiry retries via
+    /// [`TransactionOptions`].
+    pub async fn claim_stake_with_options(
+        &self,
+        storage_account_key: &Pubkey,
+        options: &TransactionOptions,
+    ) -> ShadowDriveResult<ShdwDriveResponse> {
+        let instruction = self.claim_stake_ix(storage_account_key).await?;
+        self.send_instructions(vec![instruction], options)
+    }
 
+    /// Builds the `ClaimStake` [`Instruction`] without submitting it, so callers can batch
+    /// it alongside other instructions or hand it to an external signer.
+    /// * `storage_account_key` - The public key of the [`StorageAccount`] whose accrued stake rewards will be claimed.
+    pub async fn claim_stake_ix(
+        &self,
+        storage_account_key: &Pubkey,
+    ) -> ShadowDriveResult<Instruction> {
         let selected_account = self.get_storage_account(storage_account_key).await?;
-        let unstake_account = unstake_account(&storage_account_key).0;
-        let unstake_info_account = unstake_info(&storage_account_key).0;
-        let owner_ata = get_associated_token_address(&wallet_pubkey, &TOKEN_MINT);
+        self.claim_stake_ix_with_account(storage_account_key, &selected_account)
+    }
+
+    /// Same as [`claim_stake_ix`][Self::claim_stake_ix], but takes an already-fetched
+    /// [`StorageAccount`] instead of fetching it again. Used by `claim_stake_batch` so a caller
+    /// that already validated every account up front doesn't pay for a second RPC round-trip per
+    /// account when building the instruction.
+    pub(crate) fn claim_stake_ix_with_account(
+        &self,
+        storage_account_key: &Pubkey,
+        storage_account: &StorageAccount,
+    ) -> ShadowDriveResult<Instruction> {
+        let wallet_pubkey = self.wallet.pubkey();
+        let accounts = build_claim_stake_accounts(
+            &wallet_pubkey,
+            storage_account_key,
+            storage_account.owner_1,
+        );
+
+        Ok(build_claim_stake_instruction(accounts))
+    }
+}
+
+/// Pure derivation of the `ClaimStake` account set for `storage_account_key`, split out of
+/// [`ShadowDriveClient::claim_stake_ix_with_account`] so the PDA derivations can be unit tested without an
+/// RPC client.
+fn build_claim_stake_accounts(
+    wallet_pubkey: &Pubkey,
+    storage_account_key: &Pubkey,
+    owner: Pubkey,
+) -> shdw_drive_accounts::ClaimStake {
+    let unstake_account_key = unstake_account(storage_account_key).0;
+    let unstake_info_account = unstake_info(storage_account_key).0;
+    let owner_ata = get_associated_token_address(wallet_pubkey, &TOKEN_MINT);
+
+    shdw_drive_accounts::ClaimStake {
+        storage_config: *STORAGE_CONFIG_PDA,
+        storage_account: *storage_account_key,
+        unstake_info: unstake_info_account,
+        unstake_account: unstake_account_key,
+        owner,
+        owner_ata,
+        token_mint: TOKEN_MINT,
+        system_program: system_program::ID,
+        token_program: TokenProgramID,
+    }
+}
+
+/// Pure assembly of the `ClaimStake` [`Instruction`] from its accounts, split out of
+/// [`ShadowDriveClient::claim_stake_ix_with_account`] so it can be unit tested without an RPC client.
+fn build_claim_stake_instruction(accounts: shdw_drive_accounts::ClaimStake) -> Instruction {
+    let args = ClaimStake {};
+
+    Instruction {
+        program_id: PROGRAM_ADDRESS,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn build_claim_stake_accounts_derives_expected_pdas() {
+        let wallet_pubkey = Pubkey::new_unique();
+        let storage_account_key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let accounts = build_claim_stake_accounts(&wallet_pubkey, &storage_account_key, owner);
+
+        let expected_unstake_account = unstake_account(&storage_account_key).0;
+        let expected_unstake_info = unstake_info(&storage_account_key).0;
+        let expected_owner_ata = get_associated_token_address(&wallet_pubkey, &TOKEN_MINT);
+
+        assert_eq!(accounts.storage_config, *STORAGE_CONFIG_PDA);
+        assert_eq!(accounts.storage_account, storage_account_key);
+        assert_eq!(accounts.unstake_account, expected_unstake_account);
+        assert_eq!(accounts.unstake_info, expected_unstake_info);
+        assert_eq!(accounts.owner, owner);
+        assert_eq!(accounts.owner_ata, expected_owner_ata);
+        assert_eq!(accounts.token_mint, TOKEN_MINT);
+        assert_eq!(accounts.system_program, system_program::ID);
+        assert_eq!(accounts.token_program, TokenProgramID);
+    }
+
+    #[test]
+    fn build_claim_stake_instruction_matches_manual_construction() {
+        let storage_account_key = Pubkey::new_unique();
         let accounts = shdw_drive_accounts::ClaimStake {
             storage_config: *STORAGE_CONFIG_PDA,
-            storage_account: *storage_account_key,
-            unstake_info: unstake_info_account,
-            unstake_account,
-            owner: selected_account.owner_1,
-            owner_ata,
+            storage_account: storage_account_key,
+            unstake_info: Pubkey::new_unique(),
+            unstake_account: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            owner_ata: Pubkey::new_unique(),
             token_mint: TOKEN_MINT,
             system_program: system_program::ID,
             token_program: TokenProgramID,
         };
 
-        let args = ClaimStake {};
-
-        let instruction = Instruction {
-            program_id: PROGRAM_ADDRESS,
-            accounts: accounts.to_account_metas(None),
-            data: args.data(),
+        // Reconstruct the accounts independently (same fields, different `ClaimStake` value) so
+        // the assertion can't pass by accident of sharing the same struct instance.
+        let expected_accounts = shdw_drive_accounts::ClaimStake {
+            storage_config: accounts.storage_config,
+            storage_account: accounts.storage_account,
+            unstake_info: accounts.unstake_info,
+            unstake_account: accounts.unstake_account,
+            owner: accounts.owner,
+            owner_ata: accounts.owner_ata,
+            token_mint: accounts.token_mint,
+            system_program: accounts.system_program,
+            token_program: accounts.token_program,
         };
+        let expected_args = ClaimStake {};
 
-        let txn = Transaction::new_signed_with_payer(
-            &[instruction],
-            Some(&wallet_pubkey),
-            &[&self.wallet],
-            self.rpc_client.get_latest_blockhash()?,
-        );
+        let instruction = build_claim_stake_instruction(accounts);
 
-        let txn_result = self
-            .rpc_client
-            .send_and_confirm_transaction_with_spinner_and_commitment(
-                &txn,
-                CommitmentConfig {
-                    commitment: CommitmentLevel::Confirmed,
-                },
-            )?;
-
-        Ok(ShdwDriveResponse {
-            txid: txn_result.to_string(),
-        })
+        assert_eq!(instruction.program_id, PROGRAM_ADDRESS);
+        assert_eq!(instruction.accounts, expected_accounts.to_account_metas(None));
+        assert_eq!(instruction.data, expected_args.data());
     }
 }
\ No newline at end of file
@@ -0,0 +1,52 @@
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+use super::transaction_options::{chunk_instructions_by_packet_size, TransactionOptions};
+use super::ShadowDriveClient;
+use crate::models::*;
+
+impl<T> ShadowDriveClient<T>
+where
+    T: Signer + Send + Sync,
+{
+    /// Claims stake rewards for several accounts in as few transactions as possible.
+    /// * `accounts` - Storage account keys to claim rewards for, as accepted by
+    /// [`claim_stake`][Self::claim_stake].
+    /// * `options` - Applied to every transaction submitted by the batch.
+    ///
+    /// Every account in `accounts` is validated with
+    /// [`get_storage_account`][Self::get_storage_account] up front, so a bad account returns an
+    /// error instead of silently dropping out of the batch; the fetched account is then reused to
+    /// build the instruction so each account is only fetched once. The resulting `ClaimStake`
+    /// instructions are then packed as many at a time as fit under Solana's transaction size
+    /// limit into as many transactions as needed. The returned `Vec` is keyed by account: every
+    /// account submitted in the same transaction shares that transaction's response.
+    pub async fn claim_stake_batch(
+        &self,
+        accounts: &[Pubkey],
+        options: &TransactionOptions,
+    ) -> ShadowDriveResult<Vec<(Pubkey, ShdwDriveResponse)>> {
+        let mut instructions = Vec::with_capacity(accounts.len());
+        for storage_account_key in accounts {
+            let storage_account = self.get_storage_account(storage_account_key).await?;
+            instructions.push(self.claim_stake_ix_with_account(storage_account_key, &storage_account)?);
+        }
+
+        let wallet_pubkey = self.wallet.pubkey();
+        let chunks = chunk_instructions_by_packet_size(instructions, &wallet_pubkey, options);
+
+        let mut keyed_responses = Vec::with_capacity(accounts.len());
+        let mut remaining_accounts = accounts.iter().copied();
+        for chunk in chunks {
+            let accounts_in_chunk = chunk.len();
+            let response = self.send_instructions(chunk, options)?;
+            for _ in 0..accounts_in_chunk {
+                let storage_account_key = remaining_accounts
+                    .next()
+                    .expect("chunked instruction count matches account count");
+                keyed_responses.push((storage_account_key, response.clone()));
+            }
+        }
+
+        Ok(keyed_responses)
+    }
+}
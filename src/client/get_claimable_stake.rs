@@ -0,0 +1,159 @@
+use anchor_lang::AccountDeserialize;
+use shadow_drive_user_staking::{UnstakeAccount, UnstakeInfo};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signer::Signer};
+
+use super::ShadowDriveClient;
+use crate::{derived_addresses, error::Error, models::*};
+
+/// Number of full epochs that must elapse after `epoch_last_unstaked` before `claim_stake` pays
+/// out. `shadow-drive-user-staking` enforces a one-epoch unstake cooldown as of this writing; if
+/// that ever changes on-chain, update this single constant rather than each call site.
+const UNSTAKE_COOLDOWN_EPOCHS: u64 = 1;
+
+/// Stake rewards accrued against a [`StorageAccount`]'s unstake PDAs, as reported by
+/// [`get_claimable_stake`][ShadowDriveClient::get_claimable_stake].
+#[derive(Debug, Clone, Copy)]
+pub struct ClaimableStake {
+    /// Amount of SHDW (base units) that has cleared the unstake cooldown and is claimable now.
+    pub claimable_amount: u64,
+    /// Total amount of SHDW currently moving through the unstake cooldown, claimable or not.
+    pub total_unstaked_amount: u64,
+    /// Epoch the unstake cooldown started.
+    pub unstake_epoch: u64,
+    /// Current epoch, as reported by the RPC node.
+    pub current_epoch: u64,
+    /// Epochs remaining until [`claim_stake`][ShadowDriveClient::claim_stake] would succeed;
+    /// `0` once the cooldown has cleared.
+    pub epochs_until_claimable: u64,
+    /// Whether [`claim_stake`][ShadowDriveClient::claim_stake] would succeed right now.
+    pub is_claimable: bool,
+}
+
+impl ClaimableStake {
+    /// Pure assembly of a [`ClaimableStake`] from the raw unstake amount/epochs, split out of
+    /// [`ShadowDriveClient::get_claimable_stake`] so the cooldown math can be unit tested without
+    /// an RPC client.
+    fn from_unstake_state(total_unstaked_amount: u64, unstake_epoch: u64, current_epoch: u64) -> Self {
+        let claimable_at_epoch = unstake_epoch.saturating_add(UNSTAKE_COOLDOWN_EPOCHS);
+        let epochs_until_claimable = claimable_at_epoch.saturating_sub(current_epoch);
+        let is_claimable = epochs_until_claimable == 0;
+
+        Self {
+            claimable_amount: if is_claimable { total_unstaked_amount } else { 0 },
+            total_unstaked_amount,
+            unstake_epoch,
+            current_epoch,
+            epochs_until_claimable,
+            is_claimable,
+        }
+    }
+
+    /// `ClaimableStake` for a storage account whose unstake PDAs don't exist yet, i.e. one that
+    /// has never had storage reduced. Nothing is accruing and nothing is claimable.
+    fn not_unstaking(current_epoch: u64) -> Self {
+        Self {
+            claimable_amount: 0,
+            total_unstaked_amount: 0,
+            unstake_epoch: 0,
+            current_epoch,
+            epochs_until_claimable: 0,
+            is_claimable: false,
+        }
+    }
+}
+
+impl<T> ShadowDriveClient<T>
+where
+    T: Signer + Send + Sync,
+{
+    /// Reads the `unstake_account`/`unstake_info` PDAs for `storage_account_key` and reports how
+    /// much SHDW is claimable, mirroring what `claim_stake` would pay out without submitting a
+    /// transaction. Useful for showing a user "X SHDW claimable" or "claimable in Y epochs"
+    /// ahead of time.
+    ///
+    /// Those PDAs are only created once a storage account has had storage reduced at least once
+    /// (see the `DecreaseStorage` accounts in `reduce_storage.rs`), so most storage accounts
+    /// won't have them yet; that's reported as nothing claimable rather than an error.
+    pub async fn get_claimable_stake(
+        &self,
+        storage_account_key: &Pubkey,
+    ) -> ShadowDriveResult<ClaimableStake> {
+        let (unstake_account_key, _) = derived_addresses::unstake_account(storage_account_key);
+        let (unstake_info_key, _) = derived_addresses::unstake_info(storage_account_key);
+
+        let current_epoch = self.rpc_client.get_epoch_info()?.epoch;
+
+        let unstake_account_account = self
+            .rpc_client
+            .get_account_with_commitment(&unstake_account_key, CommitmentConfig::confirmed())?
+            .value;
+        let unstake_info_account = self
+            .rpc_client
+            .get_account_with_commitment(&unstake_info_key, CommitmentConfig::confirmed())?
+            .value;
+
+        let (unstake_account_account, unstake_info_account) =
+            match (unstake_account_account, unstake_info_account) {
+                (Some(unstake_account_account), Some(unstake_info_account)) => {
+                    (unstake_account_account, unstake_info_account)
+                }
+                _ => return Ok(ClaimableStake::not_unstaking(current_epoch)),
+            };
+
+        let unstake_account =
+            UnstakeAccount::try_deserialize(&mut unstake_account_account.data.as_slice())
+                .map_err(|_| Error::InvalidUnstakeAccount)?;
+        let unstake_info =
+            UnstakeInfo::try_deserialize(&mut unstake_info_account.data.as_slice())
+                .map_err(|_| Error::InvalidUnstakeAccount)?;
+
+        Ok(ClaimableStake::from_unstake_state(
+            unstake_account.amount,
+            unstake_info.epoch_last_unstaked,
+            current_epoch,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_claimable_on_the_unstake_epoch_itself() {
+        let claimable = ClaimableStake::from_unstake_state(1_000, 10, 10);
+
+        assert!(!claimable.is_claimable);
+        assert_eq!(claimable.claimable_amount, 0);
+        assert_eq!(claimable.epochs_until_claimable, 1);
+    }
+
+    #[test]
+    fn claimable_once_cooldown_epoch_has_passed() {
+        let claimable = ClaimableStake::from_unstake_state(1_000, 10, 11);
+
+        assert!(claimable.is_claimable);
+        assert_eq!(claimable.claimable_amount, 1_000);
+        assert_eq!(claimable.epochs_until_claimable, 0);
+    }
+
+    #[test]
+    fn stays_claimable_well_past_the_cooldown() {
+        let claimable = ClaimableStake::from_unstake_state(1_000, 10, 50);
+
+        assert!(claimable.is_claimable);
+        assert_eq!(claimable.claimable_amount, 1_000);
+        assert_eq!(claimable.epochs_until_claimable, 0);
+    }
+
+    #[test]
+    fn not_unstaking_reports_nothing_claimable_instead_of_erroring() {
+        let claimable = ClaimableStake::not_unstaking(50);
+
+        assert!(!claimable.is_claimable);
+        assert_eq!(claimable.claimable_amount, 0);
+        assert_eq!(claimable.total_unstaked_amount, 0);
+        assert_eq!(claimable.epochs_until_claimable, 0);
+        assert_eq!(claimable.current_epoch, 50);
+    }
+}
@@ -2,15 +2,12 @@ use anchor_lang::{system_program, InstructionData, ToAccountMetas};
 use byte_unit::Byte;
 use shadow_drive_user_staking::accounts as shdw_drive_accounts;
 use shadow_drive_user_staking::instruction as shdw_drive_instructions;
-use solana_sdk::commitment_config::CommitmentConfig;
-use solana_sdk::commitment_config::CommitmentLevel;
 use solana_sdk::sysvar::rent;
-use solana_sdk::{
-    instruction::Instruction, pubkey::Pubkey, signer::Signer, transaction::Transaction,
-};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::ID as TokenProgramID;
 
+use super::transaction_options::TransactionOptions;
 use super::ShadowDriveClient;
 use crate::constants::EMISSIONS;
 use crate::{
@@ -59,64 +56,190 @@ where
         storage_account_key: &Pubkey,
         size: Byte,
     ) -> ShadowDriveResult<ShdwDriveResponse> {
+        self.reduce_storage_with_options(storage_account_key, size, &TransactionOptions::default())
+            .await
+    }
+
+    /// Same as [`reduce_storage`][Self::reduce_storage], but lets the caller override the
+    /// commitment level, attach a priority fee, and configure blockhash-expiry retries via
+    /// [`TransactionOptions`].
+    pub async fn reduce_storage_with_options(
+        &self,
+        storage_account_key: &Pubkey,
+        size: Byte,
+        options: &TransactionOptions,
+    ) -> ShadowDriveResult<ShdwDriveResponse> {
+        let instruction = self.reduce_storage_ix(storage_account_key, size).await?;
+        self.send_instructions(vec![instruction], options)
+    }
+
+    /// Builds the `DecreaseStorage` [`Instruction`] without submitting it, so callers can batch
+    /// it alongside other instructions or hand it to an external signer.
+    /// * `storage_account_key` - The public key of the [`StorageAccount`] whose storage will be reduced.
+    /// * `size` - The amount of storage you want to remove. See [`reduce_storage`][Self::reduce_storage]
+    /// for unit constraints.
+    pub async fn reduce_storage_ix(
+        &self,
+        storage_account_key: &Pubkey,
+        size: Byte,
+    ) -> ShadowDriveResult<Instruction> {
+        let selected_storage_acct = self.get_storage_account(storage_account_key).await?;
+        self.reduce_storage_ix_with_account(storage_account_key, size, &selected_storage_acct)
+    }
+
+    /// Same as [`reduce_storage_ix`][Self::reduce_storage_ix], but takes an already-fetched
+    /// [`StorageAccount`] instead of fetching it again. Used by `reduce_storage_batch` so a
+    /// caller that already validated every account up front doesn't pay for a second RPC
+    /// round-trip per account when building the instruction.
+    pub(crate) fn reduce_storage_ix_with_account(
+        &self,
+        storage_account_key: &Pubkey,
+        size: Byte,
+        storage_account: &StorageAccount,
+    ) -> ShadowDriveResult<Instruction> {
         let size_as_bytes: u64 = size
             .get_bytes()
             .try_into()
             .map_err(|_| Error::InvalidStorage)?;
 
         let wallet_pubkey = self.wallet.pubkey();
+        let accounts = build_decrease_storage_accounts(
+            &wallet_pubkey,
+            storage_account_key,
+            storage_account.owner_1,
+        );
+        Ok(build_decrease_storage_instruction(accounts, size_as_bytes))
+    }
+}
 
-        let selected_storage_acct = self.get_storage_account(storage_account_key).await?;
-        let (unstake_account, _) = derived_addresses::unstake_account(&storage_account_key);
-        let (unstake_info, _) = derived_addresses::unstake_info(&storage_account_key);
+/// Pure derivation of the `DecreaseStorage` account set for `storage_account_key`, split out of
+/// [`ShadowDriveClient::reduce_storage_ix_with_account`] so the PDA derivations can be unit
+/// tested without an RPC client.
+fn build_decrease_storage_accounts(
+    wallet_pubkey: &Pubkey,
+    storage_account_key: &Pubkey,
+    owner: Pubkey,
+) -> shdw_drive_accounts::DecreaseStorage {
+    let (unstake_account, _) = derived_addresses::unstake_account(storage_account_key);
+    let (unstake_info, _) = derived_addresses::unstake_info(storage_account_key);
+    let (stake_account, _) = derived_addresses::stake_account(storage_account_key);
+
+    let owner_ata = get_associated_token_address(wallet_pubkey, &TOKEN_MINT);
+    let emeissions_ata = get_associated_token_address(&EMISSIONS, &TOKEN_MINT);
+
+    shdw_drive_accounts::DecreaseStorage {
+        storage_config: *STORAGE_CONFIG_PDA,
+        storage_account: *storage_account_key,
+        unstake_info,
+        unstake_account,
+        owner,
+        owner_ata,
+        stake_account,
+        emissions_wallet: emeissions_ata,
+        token_mint: TOKEN_MINT,
+        system_program: system_program::ID,
+        token_program: TokenProgramID,
+        rent: rent::ID,
+    }
+}
 
-        let owner_ata = get_associated_token_address(&wallet_pubkey, &TOKEN_MINT);
-        let (stake_account, _) = derived_addresses::stake_account(&storage_account_key);
+/// Pure assembly of the `DecreaseStorage` [`Instruction`] from its accounts and args, split out
+/// of [`ShadowDriveClient::reduce_storage_ix_with_account`] so it can be unit tested without an
+/// RPC client.
+fn build_decrease_storage_instruction(
+    accounts: shdw_drive_accounts::DecreaseStorage,
+    remove_storage: u64,
+) -> Instruction {
+    let args = shdw_drive_instructions::DecreaseStorage {
+        remove_storage: Some(remove_storage),
+    };
 
-        let emeissions_ata = get_associated_token_address(&EMISSIONS, &TOKEN_MINT);
+    Instruction {
+        program_id: PROGRAM_ADDRESS,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
 
-        let accounts = shdw_drive_accounts::DecreaseStorage {
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dummy_accounts(storage_account_key: Pubkey) -> shdw_drive_accounts::DecreaseStorage {
+        shdw_drive_accounts::DecreaseStorage {
             storage_config: *STORAGE_CONFIG_PDA,
-            storage_account: *storage_account_key,
-            unstake_info,
-            unstake_account,
-            owner: selected_storage_acct.owner_1,
-            owner_ata,
-            stake_account,
-            emissions_wallet: emeissions_ata,
+            storage_account: storage_account_key,
+            unstake_info: Pubkey::new_unique(),
+            unstake_account: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            owner_ata: Pubkey::new_unique(),
+            stake_account: Pubkey::new_unique(),
+            emissions_wallet: Pubkey::new_unique(),
             token_mint: TOKEN_MINT,
             system_program: system_program::ID,
             token_program: TokenProgramID,
             rent: rent::ID,
-        };
-        let args = shdw_drive_instructions::DecreaseStorage {
-            remove_storage: Some(size_as_bytes),
-        };
+        }
+    }
+
+    #[test]
+    fn build_decrease_storage_accounts_derives_expected_pdas() {
+        let wallet_pubkey = Pubkey::new_unique();
+        let storage_account_key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let accounts =
+            build_decrease_storage_accounts(&wallet_pubkey, &storage_account_key, owner);
+
+        let (expected_unstake_account, _) = derived_addresses::unstake_account(&storage_account_key);
+        let (expected_unstake_info, _) = derived_addresses::unstake_info(&storage_account_key);
+        let (expected_stake_account, _) = derived_addresses::stake_account(&storage_account_key);
+        let expected_owner_ata = get_associated_token_address(&wallet_pubkey, &TOKEN_MINT);
+        let expected_emissions_ata = get_associated_token_address(&EMISSIONS, &TOKEN_MINT);
 
-        let instruction = Instruction {
-            program_id: PROGRAM_ADDRESS,
-            accounts: accounts.to_account_metas(None),
-            data: args.data(),
+        assert_eq!(accounts.storage_config, *STORAGE_CONFIG_PDA);
+        assert_eq!(accounts.storage_account, storage_account_key);
+        assert_eq!(accounts.unstake_account, expected_unstake_account);
+        assert_eq!(accounts.unstake_info, expected_unstake_info);
+        assert_eq!(accounts.stake_account, expected_stake_account);
+        assert_eq!(accounts.owner, owner);
+        assert_eq!(accounts.owner_ata, expected_owner_ata);
+        assert_eq!(accounts.emissions_wallet, expected_emissions_ata);
+        assert_eq!(accounts.token_mint, TOKEN_MINT);
+        assert_eq!(accounts.system_program, system_program::ID);
+        assert_eq!(accounts.token_program, TokenProgramID);
+        assert_eq!(accounts.rent, rent::ID);
+    }
+
+    #[test]
+    fn build_decrease_storage_instruction_matches_manual_construction() {
+        let storage_account_key = Pubkey::new_unique();
+        let accounts = dummy_accounts(storage_account_key);
+
+        // Reconstruct the accounts/args independently (same fields, different `DecreaseStorage`
+        // value) so the assertion can't pass by accident of sharing the same struct instance.
+        let expected_accounts = shdw_drive_accounts::DecreaseStorage {
+            storage_config: accounts.storage_config,
+            storage_account: accounts.storage_account,
+            unstake_info: accounts.unstake_info,
+            unstake_account: accounts.unstake_account,
+            owner: accounts.owner,
+            owner_ata: accounts.owner_ata,
+            stake_account: accounts.stake_account,
+            emissions_wallet: accounts.emissions_wallet,
+            token_mint: accounts.token_mint,
+            system_program: accounts.system_program,
+            token_program: accounts.token_program,
+            rent: accounts.rent,
+        };
+        let expected_args = shdw_drive_instructions::DecreaseStorage {
+            remove_storage: Some(1_000_000),
         };
 
-        let txn = Transaction::new_signed_with_payer(
-            &[instruction],
-            Some(&wallet_pubkey),
-            &[&self.wallet],
-            self.rpc_client.get_latest_blockhash()?,
-        );
+        let instruction = build_decrease_storage_instruction(accounts, 1_000_000);
 
-        let txn_result = self
-            .rpc_client
-            .send_and_confirm_transaction_with_spinner_and_commitment(
-                &txn,
-                CommitmentConfig {
-                    commitment: CommitmentLevel::Confirmed,
-                },
-            )?;
-
-        Ok(ShdwDriveResponse {
-            txid: txn_result.to_string(),
-        })
+        assert_eq!(instruction.program_id, PROGRAM_ADDRESS);
+        assert_eq!(instruction.accounts, expected_accounts.to_account_metas(None));
+        assert_eq!(instruction.data, expected_args.data());
     }
 }
@@ -0,0 +1,397 @@
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::message::Message;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::{instruction::Instruction, signer::Signer, transaction::Transaction};
+
+use super::ShadowDriveClient;
+use crate::models::*;
+
+/// Optional priority fee prepended to every instruction list a [`ShadowDriveClient`] sends,
+/// expressed the same way `ComputeBudgetInstruction` expects it.
+#[derive(Debug, Clone, Copy)]
+pub struct PriorityFee {
+    /// Compute-unit price, in micro-lamports. Passed straight to
+    /// `ComputeBudgetInstruction::set_compute_unit_price`.
+    pub compute_unit_price: u64,
+    /// Optional compute-unit limit. When set, `ComputeBudgetInstruction::set_compute_unit_limit`
+    /// is also prepended.
+    pub compute_unit_limit: Option<u32>,
+}
+
+/// Controls how a [`ShadowDriveClient`] confirms, prioritizes, and retries the transactions it
+/// sends. `reduce_storage_with_options`, `claim_stake_with_options`, `reduce_storage_batch` and
+/// `claim_stake_batch` accept a `TransactionOptions`; `reduce_storage` and `claim_stake` always
+/// send with [`TransactionOptions::default`] (`Confirmed`, no priority fee, no retries). Other
+/// operations, such as `create_storage_account` and `upload_file`, do not take one.
+///
+/// # Example
+///
+/// ```
+/// # use shadow_drive_rust::client::transaction_options::{TransactionOptions, PriorityFee};
+/// # use solana_sdk::commitment_config::CommitmentConfig;
+/// #
+/// let options = TransactionOptions {
+///     commitment: CommitmentConfig::finalized(),
+///     priority_fee: Some(PriorityFee {
+///         compute_unit_price: 10_000,
+///         compute_unit_limit: None,
+///     }),
+///     max_retries: 3,
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransactionOptions {
+    /// Commitment level used both to fetch the blockhash and to confirm the submitted transaction.
+    pub commitment: CommitmentConfig,
+    /// Priority fee instructions to prepend, if any.
+    pub priority_fee: Option<PriorityFee>,
+    /// Number of times to refetch the latest blockhash and resubmit after the transaction's
+    /// blockhash expires.
+    pub max_retries: u8,
+}
+
+impl Default for TransactionOptions {
+    fn default() -> Self {
+        Self {
+            commitment: CommitmentConfig::confirmed(),
+            priority_fee: None,
+            max_retries: 0,
+        }
+    }
+}
+
+impl<T> ShadowDriveClient<T>
+where
+    T: Signer + Send + Sync,
+{
+    /// Shared send path used by the `reduce_storage` and `claim_stake` methods and their batch
+    /// variants (other operations do not go through it): prepends any configured priority
+    /// fee, signs `instructions` into a single [`Transaction`], and submits it, refetching a
+    /// fresh blockhash and resubmitting up to `options.max_retries` times if the blockhash
+    /// expires before the transaction lands.
+    pub(crate) fn send_instructions(
+        &self,
+        instructions: Vec<Instruction>,
+        options: &TransactionOptions,
+    ) -> ShadowDriveResult<ShdwDriveResponse> {
+        let full_instructions = prepend_priority_fee(instructions, options);
+
+        let wallet_pubkey = self.wallet.pubkey();
+        let mut remaining_attempts: u32 = u32::from(options.max_retries).saturating_add(1);
+
+        loop {
+            let blockhash = self
+                .rpc_client
+                .get_latest_blockhash_with_commitment(options.commitment)?
+                .0;
+
+            let txn = Transaction::new_signed_with_payer(
+                &full_instructions,
+                Some(&wallet_pubkey),
+                &[&self.wallet],
+                blockhash,
+            );
+
+            match self
+                .rpc_client
+                .send_and_confirm_transaction_with_spinner_and_commitment(&txn, options.commitment)
+            {
+                Ok(signature) => return Ok(ShdwDriveResponse {
+                    txid: signature.to_string(),
+                }),
+                Err(err) if remaining_attempts > 1 && is_blockhash_expired(&err) => {
+                    remaining_attempts -= 1;
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    /// Dry-run counterpart to [`send_instructions`][Self::send_instructions]: prepends the same
+    /// priority fee, signs `instructions` into a [`Transaction`] against the latest blockhash,
+    /// and asks the RPC node to simulate it instead of submitting it. Used by `--dry-run` in the
+    /// CLI to surface on-chain errors (insufficient funds, bad account state, ...) without
+    /// spending anything.
+    pub fn simulate_instructions(
+        &self,
+        instructions: Vec<Instruction>,
+        options: &TransactionOptions,
+    ) -> ShadowDriveResult<RpcSimulateTransactionResult> {
+        let full_instructions = prepend_priority_fee(instructions, options);
+
+        let wallet_pubkey = self.wallet.pubkey();
+        let blockhash = self
+            .rpc_client
+            .get_latest_blockhash_with_commitment(options.commitment)?
+            .0;
+
+        let txn = Transaction::new_signed_with_payer(
+            &full_instructions,
+            Some(&wallet_pubkey),
+            &[&self.wallet],
+            blockhash,
+        );
+
+        Ok(self.rpc_client.simulate_transaction(&txn)?.value)
+    }
+}
+
+/// Prepends the compute-budget instructions for `options.priority_fee`, if any, ahead of
+/// `instructions`. Shared by the real send path and the dry-run simulate path so they build the
+/// exact same transaction shape.
+fn prepend_priority_fee(
+    instructions: Vec<Instruction>,
+    options: &TransactionOptions,
+) -> Vec<Instruction> {
+    let mut full_instructions = Vec::with_capacity(instructions.len() + 2);
+    if let Some(priority_fee) = &options.priority_fee {
+        full_instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+            priority_fee.compute_unit_price,
+        ));
+        if let Some(compute_unit_limit) = priority_fee.compute_unit_limit {
+            full_instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
+                compute_unit_limit,
+            ));
+        }
+    }
+    full_instructions.extend(instructions);
+    full_instructions
+}
+
+fn is_blockhash_expired(err: &ClientError) -> bool {
+    matches!(
+        err.kind(),
+        ClientErrorKind::TransactionError(TransactionError::BlockhashNotFound)
+    )
+}
+
+/// Packs `instructions` into as few transactions as possible without exceeding
+/// `PACKET_DATA_SIZE`, the maximum serialized size Solana accepts for a transaction. Shared by
+/// every batch method in the crate so the limit is measured against the real serialized message
+/// (plus the fee payer's signature) rather than guessed as a fixed instruction count.
+///
+/// Sizes each chunk as it will actually be sent: `options.priority_fee`, if set, is prepended by
+/// [`send_instructions`][ShadowDriveClient::send_instructions]/[`simulate_instructions`][ShadowDriveClient::simulate_instructions]
+/// to *every* chunk, so it's accounted for here too, not just the batch's own instructions.
+pub(crate) fn chunk_instructions_by_packet_size(
+    instructions: Vec<Instruction>,
+    payer: &Pubkey,
+    options: &TransactionOptions,
+) -> Vec<Vec<Instruction>> {
+    let priority_fee_instructions = prepend_priority_fee(Vec::new(), options);
+    let mut chunks: Vec<Vec<Instruction>> = Vec::new();
+
+    for instruction in instructions {
+        let fits_current = chunks.last().is_some_and(|current| {
+            let mut candidate = current.clone();
+            candidate.push(instruction.clone());
+            fits_in_packet(&priority_fee_instructions, &candidate, payer)
+        });
+
+        if fits_current {
+            chunks.last_mut().expect("just checked chunks is non-empty").push(instruction);
+        } else {
+            chunks.push(vec![instruction]);
+        }
+    }
+
+    chunks
+}
+
+fn fits_in_packet(
+    priority_fee_instructions: &[Instruction],
+    instructions: &[Instruction],
+    payer: &Pubkey,
+) -> bool {
+    let full_instructions: Vec<Instruction> = priority_fee_instructions
+        .iter()
+        .chain(instructions)
+        .cloned()
+        .collect();
+    let message = Message::new(&full_instructions, Some(payer));
+    // The serialized transaction is the message plus a compact-u16 signature count (1 byte for
+    // the small counts we deal with here) and one ed25519 signature (64 bytes) per required
+    // signer; every batch method in this crate only ever requires the fee payer's signature.
+    let signature_overhead = 1 + 64;
+    let message_size = bincode::serialized_size(&message).unwrap_or(u64::MAX);
+    (message_size as usize).saturating_add(signature_overhead) <= PACKET_DATA_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{system_instruction, transaction::TransactionError};
+
+    use super::*;
+
+    fn transfer(payer: &Pubkey) -> Instruction {
+        system_instruction::transfer(payer, &Pubkey::new_unique(), 1)
+    }
+
+    #[test]
+    fn prepend_priority_fee_is_a_noop_without_one_configured() {
+        let payer = Pubkey::new_unique();
+        let instructions = vec![transfer(&payer)];
+        let options = TransactionOptions::default();
+
+        let full_instructions = prepend_priority_fee(instructions.clone(), &options);
+
+        assert_eq!(full_instructions, instructions);
+    }
+
+    #[test]
+    fn prepend_priority_fee_prepends_compute_unit_price_and_limit() {
+        let payer = Pubkey::new_unique();
+        let instructions = vec![transfer(&payer)];
+        let options = TransactionOptions {
+            priority_fee: Some(PriorityFee {
+                compute_unit_price: 10_000,
+                compute_unit_limit: Some(200_000),
+            }),
+            ..TransactionOptions::default()
+        };
+
+        let full_instructions = prepend_priority_fee(instructions, &options);
+
+        assert_eq!(full_instructions.len(), 3);
+        assert_eq!(
+            full_instructions[0],
+            ComputeBudgetInstruction::set_compute_unit_price(10_000)
+        );
+        assert_eq!(
+            full_instructions[1],
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000)
+        );
+    }
+
+    #[test]
+    fn prepend_priority_fee_omits_limit_when_not_set() {
+        let payer = Pubkey::new_unique();
+        let instructions = vec![transfer(&payer)];
+        let options = TransactionOptions {
+            priority_fee: Some(PriorityFee {
+                compute_unit_price: 10_000,
+                compute_unit_limit: None,
+            }),
+            ..TransactionOptions::default()
+        };
+
+        let full_instructions = prepend_priority_fee(instructions, &options);
+
+        assert_eq!(full_instructions.len(), 2);
+        assert_eq!(
+            full_instructions[0],
+            ComputeBudgetInstruction::set_compute_unit_price(10_000)
+        );
+    }
+
+    #[test]
+    fn is_blockhash_expired_true_for_blockhash_not_found() {
+        let err = ClientError::from(ClientErrorKind::TransactionError(
+            TransactionError::BlockhashNotFound,
+        ));
+
+        assert!(is_blockhash_expired(&err));
+    }
+
+    #[test]
+    fn is_blockhash_expired_false_for_other_errors() {
+        let err = ClientError::from(ClientErrorKind::TransactionError(
+            TransactionError::AccountNotFound,
+        ));
+
+        assert!(!is_blockhash_expired(&err));
+    }
+
+    #[test]
+    fn max_retries_of_zero_allows_exactly_one_attempt() {
+        let options = TransactionOptions::default();
+
+        let remaining_attempts: u32 = u32::from(options.max_retries).saturating_add(1);
+
+        assert_eq!(remaining_attempts, 1);
+    }
+
+    #[test]
+    fn max_retries_of_u8_max_does_not_overflow() {
+        let options = TransactionOptions {
+            max_retries: u8::MAX,
+            ..TransactionOptions::default()
+        };
+
+        let remaining_attempts: u32 = u32::from(options.max_retries).saturating_add(1);
+
+        assert_eq!(remaining_attempts, u32::from(u8::MAX) + 1);
+    }
+
+    #[test]
+    fn fits_in_packet_true_when_under_the_limit() {
+        let payer = Pubkey::new_unique();
+        let instructions = vec![transfer(&payer)];
+
+        assert!(fits_in_packet(&[], &instructions, &payer));
+    }
+
+    #[test]
+    fn fits_in_packet_false_once_serialized_size_exceeds_packet_data_size() {
+        let payer = Pubkey::new_unique();
+        // Comfortably more transfer instructions than fit in a single `PACKET_DATA_SIZE` packet.
+        let instructions: Vec<Instruction> =
+            (0..(PACKET_DATA_SIZE / 10)).map(|_| transfer(&payer)).collect();
+
+        assert!(!fits_in_packet(&[], &instructions, &payer));
+    }
+
+    #[test]
+    fn chunk_instructions_by_packet_size_splits_once_the_limit_is_exceeded() {
+        let payer = Pubkey::new_unique();
+        let options = TransactionOptions::default();
+        let instructions: Vec<Instruction> =
+            (0..(PACKET_DATA_SIZE / 10)).map(|_| transfer(&payer)).collect();
+        let instruction_count = instructions.len();
+
+        let chunks = chunk_instructions_by_packet_size(instructions, &payer, &options);
+
+        assert!(chunks.len() > 1, "expected more than one chunk");
+        for chunk in &chunks {
+            assert!(fits_in_packet(&[], chunk, &payer));
+        }
+        let total: usize = chunks.iter().map(Vec::len).sum();
+        assert_eq!(total, instruction_count);
+    }
+
+    #[test]
+    fn chunk_instructions_by_packet_size_accounts_for_the_priority_fee() {
+        let payer = Pubkey::new_unique();
+        let options_without_fee = TransactionOptions::default();
+        let options_with_fee = TransactionOptions {
+            priority_fee: Some(PriorityFee {
+                compute_unit_price: 10_000,
+                compute_unit_limit: Some(200_000),
+            }),
+            ..TransactionOptions::default()
+        };
+        let instructions: Vec<Instruction> = (0..(PACKET_DATA_SIZE / 10))
+            .map(|_| transfer(&payer))
+            .collect();
+
+        let chunks_without_fee =
+            chunk_instructions_by_packet_size(instructions.clone(), &payer, &options_without_fee);
+        let chunks_with_fee =
+            chunk_instructions_by_packet_size(instructions, &payer, &options_with_fee);
+
+        assert!(
+            chunks_with_fee.len() >= chunks_without_fee.len(),
+            "accounting for the priority fee should never produce fewer chunks"
+        );
+        for chunk in &chunks_with_fee {
+            let priority_fee_instructions = prepend_priority_fee(Vec::new(), &options_with_fee);
+            assert!(fits_in_packet(&priority_fee_instructions, chunk, &payer));
+        }
+    }
+}
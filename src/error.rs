@@ -0,0 +1,15 @@
+use solana_client::client_error::ClientError;
+use thiserror::Error as ThisError;
+
+/// Errors returned by [`ShadowDriveClient`][crate::client::ShadowDriveClient] operations.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("account data could not be deserialized as a valid storage account, or the requested storage size does not fit in a u64")]
+    InvalidStorage,
+
+    #[error("account data could not be deserialized as a valid unstake account")]
+    InvalidUnstakeAccount,
+
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+}